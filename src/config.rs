@@ -0,0 +1,99 @@
+use std::fs;
+use std::path::Path;
+
+use serde::Deserialize;
+
+const CONFIG_PATH: &str = "overlay.toml";
+
+// Overlay knobs that used to be compile-time constants in `main`. Any
+// field (or the whole file) missing keeps today's hardcoded defaults.
+#[derive(Debug, Deserialize)]
+#[serde(default)]
+pub struct Config {
+    pub power_preference: PowerPreference,
+    pub backend: Backend,
+    pub present_mode: PresentMode,
+    pub font_size: f32,
+    pub show_demo_window: bool,
+}
+
+impl Default for Config {
+    fn default() -> Self {
+        Config {
+            power_preference: PowerPreference::LowPower,
+            backend: Backend::Primary,
+            present_mode: PresentMode::NoVsync,
+            font_size: 13.0,
+            show_demo_window: true,
+        }
+    }
+}
+
+impl Config {
+    // Falls back to Config::default() if overlay.toml is absent or fails to parse.
+    pub fn load() -> Self {
+        Self::load_from(CONFIG_PATH)
+    }
+
+    fn load_from<P: AsRef<Path>>(path: P) -> Self {
+        fs::read_to_string(path)
+            .ok()
+            .and_then(|contents| toml::from_str(&contents).ok())
+            .unwrap_or_default()
+    }
+}
+
+#[derive(Debug, Clone, Copy, Deserialize)]
+pub enum PowerPreference {
+    LowPower,
+    HighPerformance,
+}
+
+impl From<PowerPreference> for wgpu::PowerPreference {
+    fn from(preference: PowerPreference) -> Self {
+        match preference {
+            PowerPreference::LowPower => wgpu::PowerPreference::LowPower,
+            PowerPreference::HighPerformance => wgpu::PowerPreference::HighPerformance,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, Deserialize)]
+pub enum Backend {
+    Primary,
+    Vulkan,
+    Metal,
+    Dx12,
+    Dx11,
+    Gl,
+    All,
+}
+
+impl From<Backend> for wgpu::BackendBit {
+    fn from(backend: Backend) -> Self {
+        match backend {
+            Backend::Primary => wgpu::BackendBit::PRIMARY,
+            Backend::Vulkan => wgpu::BackendBit::VULKAN,
+            Backend::Metal => wgpu::BackendBit::METAL,
+            Backend::Dx12 => wgpu::BackendBit::DX12,
+            Backend::Dx11 => wgpu::BackendBit::DX11,
+            Backend::Gl => wgpu::BackendBit::GL,
+            Backend::All => wgpu::BackendBit::all(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, Deserialize)]
+pub enum PresentMode {
+    NoVsync,
+    Vsync,
+}
+
+impl From<PresentMode> for wgpu::PresentMode {
+    fn from(mode: PresentMode) -> Self {
+        match mode {
+            PresentMode::NoVsync => wgpu::PresentMode::NoVsync,
+            PresentMode::Vsync => wgpu::PresentMode::Vsync,
+        }
+    }
+}
@@ -0,0 +1,126 @@
+use gilrs::{Axis, Button, Gilrs};
+use imgui::{ConfigFlags, Io, NavInput};
+
+// Mirrors the in-window Shift+Escape click-through shortcut.
+const CLICKTHROUGH_TOGGLE_BUTTON: Button = Button::Start;
+
+// Drains pad events once per frame and forwards them into imgui's nav
+// input state.
+pub struct GamepadInput {
+    gilrs: Gilrs,
+}
+
+impl GamepadInput {
+    pub fn new() -> Option<Self> {
+        Gilrs::new().ok().map(|gilrs| GamepadInput { gilrs })
+    }
+
+    pub fn enable_nav(io: &mut Io) {
+        io.config_flags |= ConfigFlags::NAV_ENABLE_GAMEPAD;
+    }
+
+    // Applies nav_inputs to every io in `ios` (one per overlay monitor) and
+    // returns whether the click-through toggle button was pressed.
+    pub fn poll<'a>(&mut self, ios: impl Iterator<Item = &'a mut Io>) -> bool {
+        let mut toggle_pressed = false;
+        let mut nav_updates = Vec::new();
+
+        while let Some(gilrs::Event { event, .. }) = self.gilrs.next_event() {
+            match event {
+                gilrs::EventType::ButtonPressed(button, ..) => {
+                    if button == CLICKTHROUGH_TOGGLE_BUTTON {
+                        toggle_pressed = true;
+                    }
+                    if let Some(nav_input) = nav_input_for_button(button) {
+                        nav_updates.push((nav_input, 1.0));
+                    }
+                }
+                gilrs::EventType::ButtonReleased(button, ..) => {
+                    if let Some(nav_input) = nav_input_for_button(button) {
+                        nav_updates.push((nav_input, 0.0));
+                    }
+                }
+                gilrs::EventType::AxisChanged(axis, value, ..) => {
+                    if let Some((positive, negative)) = nav_inputs_for_axis(axis) {
+                        let (positive_value, negative_value) = nav_axis_split(value);
+                        nav_updates.push((positive, positive_value));
+                        nav_updates.push((negative, negative_value));
+                    }
+                }
+                _ => (),
+            }
+        }
+
+        for io in ios {
+            for (nav_input, value) in &nav_updates {
+                io.nav_inputs[*nav_input as usize] = *value;
+            }
+        }
+
+        toggle_pressed
+    }
+}
+
+fn nav_input_for_button(button: Button) -> Option<NavInput> {
+    match button {
+        Button::South => Some(NavInput::Activate),
+        Button::East => Some(NavInput::Cancel),
+        Button::DPadUp => Some(NavInput::DpadUp),
+        Button::DPadDown => Some(NavInput::DpadDown),
+        Button::DPadLeft => Some(NavInput::DpadLeft),
+        Button::DPadRight => Some(NavInput::DpadRight),
+        Button::LeftTrigger => Some(NavInput::FocusPrev),
+        Button::RightTrigger => Some(NavInput::FocusNext),
+        _ => None,
+    }
+}
+
+// (positive, negative) nav inputs for `axis`; caller zeroes whichever
+// direction isn't pushed, including rest.
+fn nav_inputs_for_axis(axis: Axis) -> Option<(NavInput, NavInput)> {
+    match axis {
+        Axis::LeftStickX => Some((NavInput::LStickRight, NavInput::LStickLeft)),
+        Axis::LeftStickY => Some((NavInput::LStickUp, NavInput::LStickDown)),
+        _ => None,
+    }
+}
+
+// (positive, negative) nav_inputs values for a raw axis reading; at rest
+// (value == 0.0) both sides come back 0.0, clearing whichever direction was
+// last held.
+fn nav_axis_split(value: f32) -> (f32, f32) {
+    (value.max(0.0), (-value).max(0.0))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn nav_axis_split_zeroes_both_at_rest() {
+        assert_eq!(nav_axis_split(0.0), (0.0, 0.0));
+    }
+
+    #[test]
+    fn nav_axis_split_clears_opposite_direction() {
+        assert_eq!(nav_axis_split(0.8), (0.8, 0.0));
+        assert_eq!(nav_axis_split(-0.8), (0.0, 0.8));
+    }
+
+    #[test]
+    fn nav_inputs_for_axis_maps_left_stick() {
+        assert_eq!(
+            nav_inputs_for_axis(Axis::LeftStickX),
+            Some((NavInput::LStickRight, NavInput::LStickLeft))
+        );
+        assert_eq!(
+            nav_inputs_for_axis(Axis::LeftStickY),
+            Some((NavInput::LStickUp, NavInput::LStickDown))
+        );
+    }
+
+    #[test]
+    fn nav_inputs_for_axis_ignores_unmapped_axes() {
+        assert_eq!(nav_inputs_for_axis(Axis::RightStickX), None);
+    }
+}
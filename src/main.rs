@@ -1,50 +1,64 @@
-use std::time::Instant;
-
-use imgui::*;
-use imgui_wgpu::Renderer;
-use imgui_winit_support;
 use winit::{
-    dpi::LogicalPosition,
     event::{
         DeviceEvent, ElementState, Event, KeyboardInput, ModifiersState, VirtualKeyCode,
         WindowEvent,
     },
     event_loop::{ControlFlow, EventLoop},
-    window::WindowBuilder,
+    window::Window,
 };
 
+mod config;
+mod gamepad;
 mod os;
+mod overlay;
+mod texture;
+
+use config::Config;
+use overlay::Overlay;
+
+// This wgpu version has no format-capability enumeration API to scan
+// ourselves (that arrives later, with the async Surface/Adapter redesign) —
+// get_swap_chain_preferred_format is the only format query it exposes, and
+// it already favors an sRGB-capable format where the adapter supports one.
+// Fall back to the sRGB variant, not plain Bgra8Unorm, to keep that same
+// sRGB-first preference on adapters that report nothing.
+fn preferred_swap_chain_format(adapter: &wgpu::Adapter, surface: &wgpu::Surface) -> wgpu::TextureFormat {
+    adapter
+        .get_swap_chain_preferred_format(surface)
+        .unwrap_or(wgpu::TextureFormat::Bgra8UnormSrgb)
+}
+
+// Ids for the hotkeys registered with os::register_global_hotkey.
+const HOTKEY_TOGGLE_CLICKTHROUGH: i32 = 1;
+const HOTKEY_TOGGLE_VISIBILITY: i32 = 2;
+
+// Flips overlay_clickable and applies it to every overlay window. Shared
+// by the Shift+Escape shortcut, the global click-through hotkey and the
+// gamepad toggle button.
+fn toggle_overlay_clickable<'a>(
+    windows: impl Iterator<Item = &'a Window>,
+    overlay_clickable: &mut bool,
+) {
+    for window in windows {
+        if *overlay_clickable {
+            os::make_window_overlay_clickthrough(window);
+        } else {
+            os::make_window_overlay_clickable(window);
+        }
+    }
+    *overlay_clickable = !*overlay_clickable;
+}
 
 fn main() {
     env_logger::init();
 
-    // Set up window and GPU
     let event_loop = EventLoop::new();
-    let (window, mut size, surface, hidpi_factor) = {
-        let version = env!("CARGO_PKG_VERSION");
-
-        let window = WindowBuilder::new()
-            .with_transparent(true)
-            .with_decorations(false)
-            .build(&event_loop)
-            .unwrap();
-        os::make_window_overlay(&window);
-
-        let hidpi_factor = window.hidpi_factor();
-
-        window.set_outer_position(LogicalPosition { x: 0.0, y: 0.0 });
-        window.set_inner_size(window.current_monitor().size().to_logical(hidpi_factor));
-        window.set_title(&format!("imgui-wgpu {}", version));
-        let size = window.inner_size().to_physical(hidpi_factor);
-
-        let surface = wgpu::Surface::create(&window);
 
-        (window, size, surface, hidpi_factor)
-    };
+    let mut config = Config::load();
 
     let adapter = wgpu::Adapter::request(&wgpu::RequestAdapterOptions {
-        power_preference: wgpu::PowerPreference::LowPower,
-        backends: wgpu::BackendBit::PRIMARY,
+        power_preference: config.power_preference.into(),
+        backends: config.backend.into(),
     })
     .unwrap();
 
@@ -55,60 +69,47 @@ fn main() {
         limits: wgpu::Limits::default(),
     });
 
-    // Set up swap chain
-    let mut sc_desc = wgpu::SwapChainDescriptor {
-        usage: wgpu::TextureUsage::OUTPUT_ATTACHMENT,
-        format: wgpu::TextureFormat::Bgra8Unorm,
-        width: size.width as u32,
-        height: size.height as u32,
-        present_mode: wgpu::PresentMode::NoVsync,
-    };
-
-    let mut swap_chain = device.create_swap_chain(&surface, &sc_desc);
-
-    // Set up dear imgui
-    let mut imgui = imgui::Context::create();
-    let mut platform = imgui_winit_support::WinitPlatform::init(&mut imgui);
-    platform.attach_window(
-        imgui.io_mut(),
-        &window,
-        imgui_winit_support::HiDpiMode::Default,
-    );
-    imgui.set_ini_filename(None);
-
-    let font_size = (13.0 * hidpi_factor) as f32;
-    imgui.io_mut().font_global_scale = (1.0 / hidpi_factor) as f32;
+    // One overlay per monitor, each with its own window/surface/swap chain
+    // and imgui context, so a multi-monitor rig gets covered entirely
+    // instead of just the primary display.
+    let mut overlays: Vec<Overlay> = event_loop
+        .available_monitors()
+        .map(|monitor| Overlay::new(&event_loop, &device, &mut queue, &adapter, monitor, &config))
+        .collect();
+
+    let mut gamepad = gamepad::GamepadInput::new();
+    if gamepad.is_some() {
+        for overlay in &mut overlays {
+            gamepad::GamepadInput::enable_nav(overlay.io_mut());
+        }
+    }
 
-    imgui.fonts().add_font(&[FontSource::DefaultFontData {
-        config: Some(imgui::FontConfig {
-            oversample_h: 1,
-            pixel_snap_h: true,
-            size_pixels: font_size,
+    let mut overlay_clickable = false;
+    let mut overlay_visible = true;
+
+    // Global hotkeys are tied to a window's message queue for delivery, but
+    // fire system-wide regardless of which application has focus; the first
+    // overlay's window is as good a home for them as any other.
+    os::register_global_hotkey(
+        &overlays[0].window,
+        HOTKEY_TOGGLE_CLICKTHROUGH,
+        os::HotkeyModifiers {
+            shift: true,
+            ctrl: true,
             ..Default::default()
-        }),
-    }]);
-
-    //
-    // Set up dear imgui wgpu renderer
-    //
-    let clear_color = wgpu::Color {
-        r: 0.0,
-        g: 0.0,
-        b: 0.0,
-        a: 0.0,
-    };
-    let mut renderer = Renderer::new(
-        &mut imgui,
-        &device,
-        &mut queue,
-        sc_desc.format,
-        Some(clear_color),
+        },
+        'O',
+    );
+    os::register_global_hotkey(
+        &overlays[0].window,
+        HOTKEY_TOGGLE_VISIBILITY,
+        os::HotkeyModifiers {
+            shift: true,
+            ctrl: true,
+            ..Default::default()
+        },
+        'H',
     );
-
-    let mut last_frame = Instant::now();
-    let mut demo_open = true;
-
-    let mut overlay_clickable = false;
 
     // Event loop
     event_loop.run(move |event, _, control_flow| {
@@ -117,22 +118,15 @@ fn main() {
         } else {
             ControlFlow::Poll
         };
-        match event {
+        match &event {
             Event::WindowEvent {
+                window_id,
                 event: WindowEvent::Resized(_),
                 ..
             } => {
-                size = window.inner_size().to_physical(hidpi_factor);
-
-                sc_desc = wgpu::SwapChainDescriptor {
-                    usage: wgpu::TextureUsage::OUTPUT_ATTACHMENT,
-                    format: wgpu::TextureFormat::Bgra8Unorm,
-                    width: size.width as u32,
-                    height: size.height as u32,
-                    present_mode: wgpu::PresentMode::NoVsync,
-                };
-
-                swap_chain = device.create_swap_chain(&surface, &sc_desc);
+                if let Some(overlay) = overlays.iter_mut().find(|o| o.id() == *window_id) {
+                    overlay.resize(&device);
+                }
             }
             Event::DeviceEvent {
                 event:
@@ -144,71 +138,69 @@ fn main() {
                     }),
                 ..
             } => {
-                if overlay_clickable {
-                    os::make_window_overlay_clickthrough(&window);
-                } else {
-                    os::make_window_overlay_clickable(&window);
+                toggle_overlay_clickable(
+                    overlays.iter().map(|overlay| &overlay.window),
+                    &mut overlay_clickable,
+                );
+            }
+            Event::DeviceEvent {
+                event:
+                    DeviceEvent::Key(KeyboardInput {
+                        state: ElementState::Pressed,
+                        virtual_keycode: Some(VirtualKeyCode::F5),
+                        ..
+                    }),
+                ..
+            } => {
+                config = Config::load();
+                for overlay in &mut overlays {
+                    overlay.apply_config(&device, &mut queue, &config);
                 }
-                overlay_clickable = !overlay_clickable;
             }
             Event::WindowEvent {
                 event: WindowEvent::CloseRequested,
                 ..
             } => {
+                os::unregister_global_hotkey(&overlays[0].window, HOTKEY_TOGGLE_CLICKTHROUGH);
+                os::unregister_global_hotkey(&overlays[0].window, HOTKEY_TOGGLE_VISIBILITY);
                 *control_flow = ControlFlow::Exit;
             }
             Event::EventsCleared => {
-                let now = Instant::now();
-                let delta = now - last_frame;
-                let delta_s = delta.as_micros();
-                last_frame = now;
-
-                let frame = swap_chain.get_next_texture();
-                platform
-                    .prepare_frame(imgui.io_mut(), &window)
-                    .expect("Failed to prepare frame");
-                let ui = imgui.frame();
-
-                {
-                    let window = imgui::Window::new(im_str!("Hello world"));
-                    window
-                        .size([300.0, 100.0], Condition::FirstUseEver)
-                        .build(&ui, || {
-                            ui.text(im_str!("Hello world!"));
-                            ui.text(im_str!("This...is...imgui-rs on WGPU!"));
-                            ui.separator();
-                            let mouse_pos = ui.io().mouse_pos;
-                            ui.text(im_str!(
-                                "Mouse Position: ({:.1},{:.1})",
-                                mouse_pos[0],
-                                mouse_pos[1]
-                            ));
-                        });
-
-                    let window = imgui::Window::new(im_str!("Hello too"));
-                    window
-                        .size([400.0, 200.0], Condition::FirstUseEver)
-                        .position([400.0, 200.0], Condition::FirstUseEver)
-                        .build(&ui, || {
-                            ui.text(im_str!("Frametime: {}us", delta_s));
-                        });
-
-                    ui.show_demo_window(&mut demo_open);
+                for hotkey in os::poll_global_hotkeys(&overlays[0].window) {
+                    match hotkey {
+                        HOTKEY_TOGGLE_CLICKTHROUGH => toggle_overlay_clickable(
+                            overlays.iter().map(|overlay| &overlay.window),
+                            &mut overlay_clickable,
+                        ),
+                        HOTKEY_TOGGLE_VISIBILITY => {
+                            overlay_visible = !overlay_visible;
+                            for overlay in &overlays {
+                                overlay.window.set_visible(overlay_visible);
+                            }
+                        }
+                        _ => (),
+                    }
                 }
 
-                let mut encoder: wgpu::CommandEncoder =
-                    device.create_command_encoder(&wgpu::CommandEncoderDescriptor { todo: 0 });
-
-                platform.prepare_render(&ui, &window);
-                renderer
-                    .render(ui, &mut device, &mut encoder, &frame.view)
-                    .expect("Rendering failed");
+                if let Some(gamepad) = &mut gamepad {
+                    let toggled = gamepad.poll(overlays.iter_mut().map(|overlay| overlay.io_mut()));
+                    if toggled {
+                        toggle_overlay_clickable(
+                            overlays.iter().map(|overlay| &overlay.window),
+                            &mut overlay_clickable,
+                        );
+                    }
+                }
 
-                queue.submit(&[encoder.finish()]);
+                for overlay in &mut overlays {
+                    overlay.render(&mut device, &mut queue);
+                }
             }
             _ => (),
         }
 
-        platform.handle_event(imgui.io_mut(), &window, &event);
+        for overlay in &mut overlays {
+            overlay.handle_event(&event);
+        }
     });
 }
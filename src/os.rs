@@ -0,0 +1,146 @@
+// Win32 window styling for the overlay: layered/topmost setup, the
+// click-through toggle, and global hotkey registration.
+
+use std::cell::RefCell;
+
+use winapi::shared::minwindef::{LPARAM, LRESULT, UINT, UINT_PTR, WPARAM};
+use winapi::shared::windef::HWND;
+use winapi::um::commctrl::{DefSubclassProc, SetWindowSubclass};
+use winapi::um::winuser::{
+    self, GetWindowLongW, SetWindowLongW, MOD_ALT, MOD_CONTROL, MOD_SHIFT, MOD_WIN, WM_HOTKEY,
+    WS_EX_LAYERED, WS_EX_TRANSPARENT,
+};
+use winit::platform::windows::WindowExtWindows;
+use winit::window::Window;
+
+thread_local! {
+    // WM_HOTKEY ids seen by `hotkey_subclass_proc` since the last poll. A
+    // subclass proc runs on the same thread as the window it's attached to,
+    // so a thread-local is enough without any locking.
+    static PENDING_HOTKEYS: RefCell<Vec<i32>> = RefCell::new(Vec::new());
+}
+
+const HOTKEY_SUBCLASS_ID: UINT_PTR = 1;
+
+fn hwnd(window: &Window) -> HWND {
+    window.hwnd() as HWND
+}
+
+pub fn make_window_overlay(window: &Window) {
+    let hwnd = hwnd(window);
+    unsafe {
+        let ex_style = GetWindowLongW(hwnd, winuser::GWL_EXSTYLE);
+        SetWindowLongW(hwnd, winuser::GWL_EXSTYLE, ex_style | WS_EX_LAYERED as i32);
+        winuser::SetWindowPos(
+            hwnd,
+            winuser::HWND_TOPMOST,
+            0,
+            0,
+            0,
+            0,
+            winuser::SWP_NOMOVE | winuser::SWP_NOSIZE,
+        );
+    }
+}
+
+pub fn make_window_overlay_clickthrough(window: &Window) {
+    set_transparent(window, true);
+}
+
+pub fn make_window_overlay_clickable(window: &Window) {
+    set_transparent(window, false);
+}
+
+fn set_transparent(window: &Window, transparent: bool) {
+    let hwnd = hwnd(window);
+    unsafe {
+        let ex_style = GetWindowLongW(hwnd, winuser::GWL_EXSTYLE);
+        let ex_style = if transparent {
+            ex_style | WS_EX_TRANSPARENT as i32
+        } else {
+            ex_style & !(WS_EX_TRANSPARENT as i32)
+        };
+        SetWindowLongW(hwnd, winuser::GWL_EXSTYLE, ex_style);
+    }
+}
+
+// Modifiers for a global hotkey combo, kept winapi-free so callers outside
+// this module don't need to depend on it.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct HotkeyModifiers {
+    pub shift: bool,
+    pub ctrl: bool,
+    pub alt: bool,
+    pub win: bool,
+}
+
+impl HotkeyModifiers {
+    fn to_win32(self) -> u32 {
+        let mut mods = 0;
+        if self.shift {
+            mods |= MOD_SHIFT;
+        }
+        if self.ctrl {
+            mods |= MOD_CONTROL;
+        }
+        if self.alt {
+            mods |= MOD_ALT;
+        }
+        if self.win {
+            mods |= MOD_WIN;
+        }
+        mods as u32
+    }
+}
+
+// Registers a system-wide hotkey (fires even while unfocused) identified by
+// `id`. Delivery happens through `poll_global_hotkeys`. `key` is an ASCII
+// letter or digit, since Win32 virtual key codes for those match ASCII.
+//
+// winit's own Win32 message pump drains WM_HOTKEY before it ever reaches a
+// public winit `Event`, so registering the hotkey isn't enough on its own;
+// `SetWindowSubclass` hooks the window procedure ahead of winit's handling
+// to catch WM_HOTKEY as it comes in.
+pub fn register_global_hotkey(window: &Window, id: i32, modifiers: HotkeyModifiers, key: char) -> bool {
+    if !key.is_ascii_alphanumeric() {
+        return false;
+    }
+    let vk = key.to_ascii_uppercase() as u32;
+    let hwnd = hwnd(window);
+    unsafe {
+        // Idempotent: re-subclassing with the same id just updates dwRefData.
+        SetWindowSubclass(hwnd, Some(hotkey_subclass_proc), HOTKEY_SUBCLASS_ID, 0);
+        winuser::RegisterHotKey(hwnd, id, modifiers.to_win32(), vk) != 0
+    }
+}
+
+pub fn unregister_global_hotkey(window: &Window, id: i32) {
+    let hwnd = hwnd(window);
+    unsafe {
+        winuser::UnregisterHotKey(hwnd, id);
+    }
+}
+
+// Subclass proc installed ahead of winit's WndProc handling; forwards
+// WM_HOTKEY to the pending queue instead of letting it fall through to
+// DefWindowProc unseen.
+unsafe extern "system" fn hotkey_subclass_proc(
+    hwnd: HWND,
+    msg: UINT,
+    wparam: WPARAM,
+    lparam: LPARAM,
+    _uidsubclass: UINT_PTR,
+    _dwrefdata: usize,
+) -> LRESULT {
+    if msg == WM_HOTKEY {
+        PENDING_HOTKEYS.with(|pending| pending.borrow_mut().push(wparam as i32));
+        return 0;
+    }
+    DefSubclassProc(hwnd, msg, wparam, lparam)
+}
+
+// Drains the WM_HOTKEY ids caught by `hotkey_subclass_proc` since the last
+// poll. Call once per frame.
+pub fn poll_global_hotkeys(_window: &Window) -> Vec<i32> {
+    PENDING_HOTKEYS.with(|pending| pending.borrow_mut().drain(..).collect())
+}
@@ -0,0 +1,267 @@
+use std::time::Instant;
+
+use imgui::{im_str, Condition, FontConfig, FontSource};
+use imgui_wgpu::Renderer;
+use winit::{
+    dpi::LogicalPosition,
+    event::Event,
+    event_loop::EventLoop,
+    monitor::MonitorHandle,
+    window::{Window, WindowBuilder, WindowId},
+};
+
+use crate::config::Config;
+use crate::texture::{self, OverlayTexture};
+use crate::{os, preferred_swap_chain_format};
+
+// Size of the procedurally animated demo texture (re-uploaded each frame).
+const LIVE_TEXTURE_SIZE: u32 = 32;
+
+// One monitor's window, surface/swap chain, and imgui context/renderer,
+// kept independent so a multi-monitor rig gets one overlay per screen.
+pub struct Overlay {
+    pub window: Window,
+    surface: wgpu::Surface,
+    sc_desc: wgpu::SwapChainDescriptor,
+    swap_chain: wgpu::SwapChain,
+    imgui: imgui::Context,
+    platform: imgui_winit_support::WinitPlatform,
+    renderer: Renderer,
+    logo: Option<OverlayTexture>,
+    live_texture: OverlayTexture,
+    start_time: Instant,
+    demo_open: bool,
+    show_demo_window: bool,
+    last_frame: Instant,
+    hidpi_factor: f64,
+}
+
+impl Overlay {
+    // Creates a transparent, decorationless, click-through window covering
+    // exactly `monitor`, with its own GPU surface and imgui context.
+    pub fn new(
+        event_loop: &EventLoop<()>,
+        device: &wgpu::Device,
+        queue: &mut wgpu::Queue,
+        adapter: &wgpu::Adapter,
+        monitor: MonitorHandle,
+        config: &Config,
+    ) -> Self {
+        let version = env!("CARGO_PKG_VERSION");
+
+        let window = WindowBuilder::new()
+            .with_transparent(true)
+            .with_decorations(false)
+            .build(event_loop)
+            .unwrap();
+        os::make_window_overlay(&window);
+        os::make_window_overlay_clickthrough(&window);
+
+        let hidpi_factor = monitor.hidpi_factor();
+        let monitor_position = monitor.position().to_logical(hidpi_factor);
+        window.set_outer_position(LogicalPosition {
+            x: monitor_position.x,
+            y: monitor_position.y,
+        });
+        window.set_inner_size(monitor.size().to_logical(hidpi_factor));
+        window.set_title(&format!("imgui-wgpu {}", version));
+
+        let size = window.inner_size().to_physical(hidpi_factor);
+        let surface = wgpu::Surface::create(&window);
+        let swap_chain_format = preferred_swap_chain_format(adapter, &surface);
+
+        let sc_desc = wgpu::SwapChainDescriptor {
+            usage: wgpu::TextureUsage::OUTPUT_ATTACHMENT,
+            format: swap_chain_format,
+            width: size.width as u32,
+            height: size.height as u32,
+            present_mode: config.present_mode.into(),
+        };
+        let swap_chain = device.create_swap_chain(&surface, &sc_desc);
+
+        let mut imgui = imgui::Context::create();
+        let mut platform = imgui_winit_support::WinitPlatform::init(&mut imgui);
+        platform.attach_window(
+            imgui.io_mut(),
+            &window,
+            imgui_winit_support::HiDpiMode::Default,
+        );
+        imgui.set_ini_filename(None);
+
+        let font_size = config.font_size * hidpi_factor as f32;
+        imgui.io_mut().font_global_scale = (1.0 / hidpi_factor) as f32;
+        imgui.fonts().add_font(&[FontSource::DefaultFontData {
+            config: Some(FontConfig {
+                oversample_h: 1,
+                pixel_snap_h: true,
+                size_pixels: font_size,
+                ..Default::default()
+            }),
+        }]);
+
+        let clear_color = wgpu::Color {
+            r: 0.0,
+            g: 0.0,
+            b: 0.0,
+            a: 0.0,
+        };
+        let mut renderer = Renderer::new(&mut imgui, device, queue, sc_desc.format, Some(clear_color));
+
+        // Optional logo shown in the "Hello world" window, if present in
+        // the working directory. Missing/unreadable images are not fatal.
+        let logo = texture::load_texture_from_file(
+            device,
+            queue,
+            &mut renderer,
+            "logo.png",
+            wgpu::FilterMode::Linear,
+        )
+        .ok();
+
+        let live_texture = texture::create_blank_texture(
+            device,
+            &mut renderer,
+            LIVE_TEXTURE_SIZE,
+            LIVE_TEXTURE_SIZE,
+            wgpu::FilterMode::Nearest,
+        );
+
+        Overlay {
+            window,
+            surface,
+            sc_desc,
+            swap_chain,
+            imgui,
+            platform,
+            renderer,
+            logo,
+            live_texture,
+            start_time: Instant::now(),
+            demo_open: true,
+            show_demo_window: config.show_demo_window,
+            last_frame: Instant::now(),
+            hidpi_factor,
+        }
+    }
+
+    // Applies a freshly (re)loaded config: present mode, font size, demo
+    // window visibility.
+    pub fn apply_config(&mut self, device: &wgpu::Device, queue: &mut wgpu::Queue, config: &Config) {
+        self.show_demo_window = config.show_demo_window;
+
+        let present_mode = config.present_mode.into();
+        if self.sc_desc.present_mode != present_mode {
+            self.sc_desc.present_mode = present_mode;
+            self.swap_chain = device.create_swap_chain(&self.surface, &self.sc_desc);
+        }
+
+        let font_size = config.font_size * self.hidpi_factor as f32;
+        self.imgui.fonts().clear();
+        self.imgui.io_mut().font_global_scale = (1.0 / self.hidpi_factor) as f32;
+        self.imgui.fonts().add_font(&[FontSource::DefaultFontData {
+            config: Some(FontConfig {
+                oversample_h: 1,
+                pixel_snap_h: true,
+                size_pixels: font_size,
+                ..Default::default()
+            }),
+        }]);
+        self.renderer.reload_font_texture(&mut self.imgui, device, queue);
+    }
+
+    pub fn id(&self) -> WindowId {
+        self.window.id()
+    }
+
+    pub fn io_mut(&mut self) -> &mut imgui::Io {
+        self.imgui.io_mut()
+    }
+
+    // Recreates the swap chain at the window's current size.
+    pub fn resize(&mut self, device: &wgpu::Device) {
+        let size = self.window.inner_size().to_physical(self.hidpi_factor);
+        self.sc_desc.width = size.width as u32;
+        self.sc_desc.height = size.height as u32;
+        self.swap_chain = device.create_swap_chain(&self.surface, &self.sc_desc);
+    }
+
+    pub fn handle_event(&mut self, event: &Event<()>) {
+        self.platform
+            .handle_event(self.imgui.io_mut(), &self.window, event);
+    }
+
+    // Renders one frame of this monitor's overlay.
+    pub fn render(&mut self, device: &mut wgpu::Device, queue: &mut wgpu::Queue) {
+        let now = Instant::now();
+        let delta = now - self.last_frame;
+        let delta_s = delta.as_micros();
+        self.last_frame = now;
+
+        // Pulse the live texture's brightness each frame to exercise the
+        // dynamic-update path (vs. the logo, which is loaded once).
+        let brightness = ((self.start_time.elapsed().as_secs_f32().sin() * 0.5 + 0.5) * 255.0) as u8;
+        let pixel_count = (LIVE_TEXTURE_SIZE * LIVE_TEXTURE_SIZE) as usize;
+        let live_pixels: Vec<u8> = std::iter::repeat([brightness, 0, brightness, 255])
+            .take(pixel_count)
+            .flatten()
+            .collect();
+        texture::update_texture(&self.renderer, queue, &self.live_texture, &live_pixels);
+
+        let frame = self.swap_chain.get_next_texture();
+        self.platform
+            .prepare_frame(self.imgui.io_mut(), &self.window)
+            .expect("Failed to prepare frame");
+        let ui = self.imgui.frame();
+
+        {
+            let window = imgui::Window::new(im_str!("Hello world"));
+            window
+                .size([300.0, 100.0], Condition::FirstUseEver)
+                .build(&ui, || {
+                    ui.text(im_str!("Hello world!"));
+                    ui.text(im_str!("This...is...imgui-rs on WGPU!"));
+                    ui.separator();
+                    let mouse_pos = ui.io().mouse_pos;
+                    ui.text(im_str!(
+                        "Mouse Position: ({:.1},{:.1})",
+                        mouse_pos[0],
+                        mouse_pos[1]
+                    ));
+
+                    if let Some(logo) = &self.logo {
+                        ui.separator();
+                        imgui::Image::new(logo.id, [logo.width, logo.height]).build(&ui);
+                    }
+
+                    ui.separator();
+                    imgui::Image::new(
+                        self.live_texture.id,
+                        [self.live_texture.width, self.live_texture.height],
+                    )
+                    .build(&ui);
+                });
+
+            let window = imgui::Window::new(im_str!("Hello too"));
+            window
+                .size([400.0, 200.0], Condition::FirstUseEver)
+                .position([400.0, 200.0], Condition::FirstUseEver)
+                .build(&ui, || {
+                    ui.text(im_str!("Frametime: {}us", delta_s));
+                });
+
+            if self.show_demo_window {
+                ui.show_demo_window(&mut self.demo_open);
+            }
+        }
+
+        let mut encoder: wgpu::CommandEncoder =
+            device.create_command_encoder(&wgpu::CommandEncoderDescriptor { todo: 0 });
+
+        self.platform.prepare_render(&ui, &self.window);
+        self.renderer
+            .render(ui, device, &mut encoder, &frame.view)
+            .expect("Rendering failed");
+
+        queue.submit(&[encoder.finish()]);
+    }
+}
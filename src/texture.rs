@@ -0,0 +1,106 @@
+use std::path::Path;
+
+use image::GenericImageView;
+use imgui::TextureId;
+use imgui_wgpu::{Renderer, Texture, TextureConfig};
+
+// Uploaded and registered with the renderer; draw with
+// imgui::Image::new(tex_id, [w, h]).build(&ui).
+pub struct OverlayTexture {
+    pub id: TextureId,
+    pub width: f32,
+    pub height: f32,
+}
+
+// Decodes an image file (PNG/JPEG/etc via `image::open`) and uploads it
+// as a texture sized to the decoded dimensions.
+pub fn load_texture_from_file<P: AsRef<Path>>(
+    device: &wgpu::Device,
+    queue: &mut wgpu::Queue,
+    renderer: &mut Renderer,
+    path: P,
+    filter: wgpu::FilterMode,
+) -> Result<OverlayTexture, image::ImageError> {
+    let image = image::open(path)?;
+    let (width, height) = image.dimensions();
+    let data = image.to_rgba().into_raw();
+
+    let config = TextureConfig {
+        size: wgpu::Extent3d {
+            width,
+            height,
+            depth: 1,
+        },
+        label: None,
+        filter,
+        ..Default::default()
+    };
+
+    let texture = Texture::new(device, renderer, config);
+    texture.write(queue, &data, width, height);
+
+    let id = renderer.textures.insert(texture);
+
+    Ok(OverlayTexture {
+        id,
+        width: width as f32,
+        height: height as f32,
+    })
+}
+
+// Registers a texture with no file backing, for generated content updated
+// via `update_texture` each frame.
+pub fn create_blank_texture(
+    device: &wgpu::Device,
+    renderer: &mut Renderer,
+    width: u32,
+    height: u32,
+    filter: wgpu::FilterMode,
+) -> OverlayTexture {
+    let config = TextureConfig {
+        size: wgpu::Extent3d {
+            width,
+            height,
+            depth: 1,
+        },
+        label: None,
+        filter,
+        ..Default::default()
+    };
+
+    let texture = Texture::new(device, renderer, config);
+    let id = renderer.textures.insert(texture);
+
+    OverlayTexture {
+        id,
+        width: width as f32,
+        height: height as f32,
+    }
+}
+
+// Re-uploads `data` (tightly packed RGBA8, width * height * 4 bytes) into
+// an already-registered texture.
+pub fn update_texture(
+    renderer: &Renderer,
+    queue: &mut wgpu::Queue,
+    tex: &OverlayTexture,
+    data: &[u8],
+) {
+    let expected_len = tex.width as usize * tex.height as usize * 4;
+    assert_eq!(
+        data.len(),
+        expected_len,
+        "update_texture: data is {} bytes, expected {} ({}x{} RGBA8)",
+        data.len(),
+        expected_len,
+        tex.width,
+        tex.height,
+    );
+
+    let texture = renderer
+        .textures
+        .get(tex.id)
+        .expect("texture was dropped from the renderer's texture table");
+
+    texture.write(queue, data, tex.width as u32, tex.height as u32);
+}